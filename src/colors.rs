@@ -0,0 +1,8 @@
+// Shared hex color palette, referenced by the theme modules in `themes`.
+
+pub const BACKGROUND: &str = "282a36";
+pub const FOREGROUND: &str = "f8f8f2";
+pub const COMMENT: &str = "6272a4";
+pub const PURPLE: &str = "bd93f9";
+pub const GREEN: &str = "50fa7b";
+pub const RED: &str = "ff5555";
@@ -0,0 +1,80 @@
+use crate::maps::corridors::CorridorsMap;
+use crate::Direction;
+
+use rand_pcg::Pcg64;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// Path, relative to the working directory, of the user-editable map/run config.
+const CONFIG_PATH: &str = "hebi.json5";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Cell {
+    Empty,
+    Wall,
+    Spawn(Direction),
+}
+
+pub struct MapData {
+    pub width: u32,
+    pub height: u32,
+    pub cells: HashMap<(u32, u32), Cell>,
+}
+
+pub trait MapType {
+    fn get_map_data(&self, generator: &mut Pcg64) -> MapData;
+}
+
+// Tagged union of the generators a `hebi.json5` file can select via `"type"`.
+// Only `Corridors` is implemented today; add variants here (`Open`, `Box`, ...)
+// as more `MapType`s are written.
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+pub enum MapConfig {
+    Corridors(CorridorsMap),
+}
+
+impl Default for MapConfig {
+    fn default() -> Self {
+        Self::Corridors(CorridorsMap::default())
+    }
+}
+
+impl MapType for MapConfig {
+    fn get_map_data(&self, generator: &mut Pcg64) -> MapData {
+        match self {
+            Self::Corridors(map) => map.get_map_data(generator),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub map: MapConfig,
+    pub seed: u64,
+    // Path to a recorded input log to replay instead of reading the keyboard.
+    pub replay: Option<String>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            map: MapConfig::default(),
+            seed: 0,
+            replay: None,
+        }
+    }
+}
+
+// Reads `CONFIG_PATH` and parses it as JSON5, falling back to defaults if the
+// file is missing or malformed so the game stays playable out of the box.
+pub fn load() -> GameConfig {
+    match std::fs::read_to_string(CONFIG_PATH) {
+        Ok(contents) => json5::from_str(&contents).unwrap_or_else(|error| {
+            eprintln!("Failed to parse {}: {}, falling back to defaults", CONFIG_PATH, error);
+            GameConfig::default()
+        }),
+        Err(_) => GameConfig::default(),
+    }
+}
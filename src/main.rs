@@ -1,73 +1,207 @@
 use bevy::prelude::*;
-use bevy::core::FixedTimestep;
+use bevy::ecs::schedule::ShouldRun;
+
+use rand::prelude::*;
+use rand_pcg::Pcg64;
+use serde::{Deserialize, Serialize};
 
 #[allow(unused)] mod colors;
 #[allow(unused)] mod themes;
+pub(crate) mod config;
+pub(crate) mod maps;
 
+use config::{Cell, MapData, MapType};
 use themes::dracula as theme;
 
-// World width in grid cells
-const GRID_WIDTH: u32 = 29;
-
-// World height in grid cells
-const GRID_HEIGHT: u32 = 29;
-
 // Pixel dimension of grid cell
 const GRID_SCALE: u32 = 24;
 
 // Pixel padding outside of grid
 const GRID_PADDING: u32 = 24;
 
+// Movement interval, in seconds, before the snake has eaten anything
+const BASE_MOVEMENT_INTERVAL: f64 = 0.125;
+
+// How much each food eaten speeds the movement interval up by
+const DIFFICULTY_FACTOR: f64 = 0.05;
+
+// How many movement ticks are skipped between food spawn attempts; an attempt
+// spawns nothing while food is already on the grid. Counted in movement ticks
+// rather than wall time so a replay reproduces food placement.
+const FOOD_SPAWN_INTERVAL_TICKS: u32 = 16;
+
 fn main() {
-    App::build()
+    let game_config = config::load();
+    let game_seed = GameSeed(game_config.seed);
+    let mut generator = Pcg64::seed_from_u64(game_seed.0);
+    let map_data = game_config.map.get_map_data(&mut generator);
+    let dimensions = GridDimensions {
+        width: map_data.width,
+        height: map_data.height,
+    };
+
+    let title = "Hebi".to_string();
+    let width = (dimensions.width * GRID_SCALE + GRID_PADDING * 2) as f32;
+    let height = (dimensions.height * GRID_SCALE + GRID_PADDING * 2) as f32;
+    println!(
+        "Configuring window with a title of '{}', a width of {} pixels, and a height of {} pixels.",
+        title, width, height
+    );
+
+    let mut app = App::build();
+    app
+        .insert_resource(map_data)
+        .insert_resource(dimensions)
+        .insert_resource(game_seed)
         .add_startup_system(setup.system())
+        .add_startup_stage("map_spawn", SystemStage::single(map_spawn.system()))
         .add_startup_stage("world_spawn", SystemStage::single(world_spawn.system()))
         .add_startup_stage("snake_spawn", SystemStage::single(snake_spawn.system()))
-        .add_system(snake_movement_input.system())
+        .add_event::<GrowthEvent>()
+        .add_event::<GameOverEvent>()
+        .add_system_to_stage(CoreStage::PreUpdate, update_difficulty.system())
+        .add_system(restart.system())
+        .add_system(score_display.system())
         .add_system_set(
             SystemSet::new()
-                .with_run_criteria(FixedTimestep::step(0.125))
-                .with_system(snake_movement.system())
+                .with_run_criteria(movement_timestep.system())
+                .with_system(collision_detection.system().label("collision_detection"))
+                .with_system(handle_game_over.system().label("handle_game_over").after("collision_detection"))
+                .with_system(snake_movement.system().label("snake_movement").after("handle_game_over"))
+                .with_system(food_eating.system().after("snake_movement"))
+                .with_system(food_spawn.system().after("snake_movement"))
                 .with_system(tick.system())
         )
         .add_system_to_stage(CoreStage::PostUpdate, grid_positioning.system())
-        .insert_resource({
-            let title = "Hebi".to_string();
-            let width = (GRID_WIDTH * GRID_SCALE + GRID_PADDING * 2) as f32;
-            let height = (GRID_HEIGHT * GRID_SCALE + GRID_PADDING * 2) as f32;
-            println!(
-                "Configuring window with a title of '{}', a width of {} pixels, and a height of {} pixels.",
-                title, width, height
-            );
-            WindowDescriptor {
-                title,
-                width,
-                height,
-                resizable: false,
-                ..Default::default()
-            }
+        .insert_resource(WindowDescriptor {
+            title,
+            width,
+            height,
+            resizable: false,
+            ..Default::default()
         })
-        .insert_resource(ClearColor(Color::hex(theme::BACKGROUND).unwrap()))
-        .add_plugins(DefaultPlugins)
-        .run();
+        .insert_resource(ClearColor(Color::hex(theme::BACKGROUND).unwrap()));
+
+    // A replay path makes the run deterministic and keyboard-free, reproducing
+    // the exact sequence of direction changes recorded against the same seed.
+    match &game_config.replay {
+        Some(path) => {
+            app.insert_resource(load_replay(path));
+            app.add_system(snake_movement_replay.system());
+        }
+        None => {
+            app.insert_resource(InputLog::default());
+            app.add_system(snake_movement_input.system());
+        }
+    }
+
+    app.add_plugins(DefaultPlugins).run();
+}
+
+fn load_replay(path: &str) -> ReplayLog {
+    let entries = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| json5::from_str(&contents).ok())
+        .unwrap_or_else(|| {
+            eprintln!("Failed to load replay from {}, starting with no recorded input", path);
+            Vec::new()
+        });
+    ReplayLog { entries, cursor: 0 }
 }
 
 fn setup(
     mut commands: Commands,
     materials: ResMut<Assets<ColorMaterial>>,
+    asset_server: Res<AssetServer>,
+    game_seed: Res<GameSeed>,
 ) {
     commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    commands.spawn_bundle(UiCameraBundle::default());
     commands.insert_resource(Materials::new(materials));
     commands.insert_resource(Clock::default());
+    commands.insert_resource(LastTailPosition::default());
+    commands.insert_resource(FoodSpawnTimer::default());
+    commands.insert_resource(GameState::default());
+    commands.insert_resource(Score::default());
+    commands.insert_resource(MovementInterval::default());
+    commands.insert_resource(FoodRng(Pcg64::seed_from_u64(game_seed.0)));
+
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(GRID_PADDING as f32 * 0.5),
+                    left: Val::Px(GRID_PADDING as f32 * 0.5),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 20.0,
+                    color: Color::hex(theme::SNAKE).unwrap(),
+                },
+                TextAlignment::default(),
+            ),
+            ..Default::default()
+        })
+        .insert(ScoreText);
+}
+
+fn map_spawn(
+    mut commands: Commands,
+    materials: Res<Materials>,
+    dimensions: Res<GridDimensions>,
+    map_data: Res<MapData>,
+) {
+    for (&(x, y), cell) in map_data.cells.iter() {
+        if let Cell::Wall = cell {
+            let grid_position = GridPosition::new(x as i32, y as i32);
+            commands
+                .spawn_bundle(SpriteBundle {
+                    material: materials.wall.clone(),
+                    sprite: Sprite::new(Vec2::new(GRID_SCALE as f32, GRID_SCALE as f32)),
+                    transform: Transform::from_translation(grid_to_vector(&grid_position, &dimensions)),
+                    ..Default::default()
+                })
+                .insert(Wall)
+                .insert(grid_position);
+        }
+    }
+
+    // Take the lowest spawn cell so the choice does not depend on HashMap order.
+    let (spawn_position, spawn_direction) = map_data
+        .cells
+        .iter()
+        .filter_map(|(&(x, y), cell)| match cell {
+            Cell::Spawn(direction) => Some(((x, y), *direction)),
+            _ => None,
+        })
+        .min_by_key(|&((x, y), _)| (y, x))
+        .map(|((x, y), direction)| (GridPosition::new(x as i32, y as i32), direction))
+        .unwrap_or_else(|| {
+            (
+                GridPosition::new((dimensions.width / 2) as i32, (dimensions.height / 2) as i32),
+                Direction::Up,
+            )
+        });
+
+    commands.insert_resource(SpawnPoint {
+        position: spawn_position,
+        direction: spawn_direction,
+    });
 }
 
 fn grid_positioning(
     mut query: Query<(&GridPosition, &mut Transform)>,
+    dimensions: Res<GridDimensions>,
 ) {
     for (grid_position, mut transform) in query.iter_mut() {
-        assert!(grid_position.in_bounds());
         transform.translation = transform.translation.lerp(
-            grid_to_vector(grid_position),
+            grid_to_vector(grid_position, &dimensions),
             match grid_position.t {
                 Some(t) => t,
                 None => 1.0,
@@ -76,10 +210,10 @@ fn grid_positioning(
     }
 }
 
-fn grid_to_vector(grid_position: &GridPosition) -> Vec3 {
+fn grid_to_vector(grid_position: &GridPosition, dimensions: &GridDimensions) -> Vec3 {
     Vec3::new(
-        (grid_position.x as f32 - GRID_WIDTH as f32 / 2.0) * GRID_SCALE as f32 + GRID_SCALE as f32 / 2.0,
-        (grid_position.y as f32 - GRID_HEIGHT as f32 / 2.0) * GRID_SCALE as f32 + GRID_SCALE as f32 / 2.0,
+        (grid_position.x as f32 - dimensions.width as f32 / 2.0) * GRID_SCALE as f32 + GRID_SCALE as f32 / 2.0,
+        (grid_position.y as f32 - dimensions.height as f32 / 2.0) * GRID_SCALE as f32 + GRID_SCALE as f32 / 2.0,
         0.0,
     )
 }
@@ -87,14 +221,15 @@ fn grid_to_vector(grid_position: &GridPosition) -> Vec3 {
 fn world_spawn(
     mut commands: Commands,
     materials: Res<Materials>,
+    dimensions: Res<GridDimensions>,
 ) {
     commands
         .spawn_bundle(SpriteBundle {
             material: materials.grid_background.clone(),
             sprite: Sprite::new(
                 Vec2::new(
-                    (GRID_WIDTH * GRID_SCALE) as f32,
-                    (GRID_HEIGHT * GRID_SCALE) as f32
+                    (dimensions.width * GRID_SCALE) as f32,
+                    (dimensions.height * GRID_SCALE) as f32
                 )
             ),
             ..Default::default()
@@ -104,23 +239,33 @@ fn world_spawn(
 fn snake_spawn(
     mut commands: Commands,
     materials: Res<Materials>,
+    dimensions: Res<GridDimensions>,
+    spawn_point: Res<SpawnPoint>,
+) {
+    spawn_snake(&mut commands, &materials, &dimensions, &spawn_point);
+}
+
+fn spawn_snake(
+    commands: &mut Commands,
+    materials: &Materials,
+    dimensions: &GridDimensions,
+    spawn_point: &SpawnPoint,
 ) {
-    const DIRECTION: Direction = Direction::Up;
     const SEGMENTS: u32 = 7;
-    let mut snake_head = SnakeHead::new(DIRECTION);
-    let snake_head_position = GridPosition::center();
+    let mut snake_head = SnakeHead::new(spawn_point.direction);
+    let snake_head_position = spawn_point.position.clone();
     let segment_direction = snake_head.direction.opposite().vec();
     for i in 1..SEGMENTS {
-        snake_head.spawn_segment(&mut commands, &materials, GridPosition::new(
-            ((segment_direction.x * (i as f32)) + snake_head_position.x as f32) as u32,
-            ((segment_direction.y * (i as f32)) + snake_head_position.y as f32) as u32,
+        snake_head.spawn_segment(commands, materials, dimensions, GridPosition::new(
+            ((segment_direction.x * (i as f32)) + snake_head_position.x as f32) as i32,
+            ((segment_direction.y * (i as f32)) + snake_head_position.y as f32) as i32,
         ))
     }
     commands
         .spawn_bundle(SpriteBundle {
             material: materials.snake.clone(),
             sprite: Sprite::new(Vec2::new(GRID_SCALE as f32 * 0.875, GRID_SCALE as f32 * 0.875)),
-            transform: Transform::from_translation(grid_to_vector(&snake_head_position)),
+            transform: Transform::from_translation(grid_to_vector(&snake_head_position, dimensions)),
             ..Default::default()
         })
         .insert(snake_head_position)
@@ -128,9 +273,15 @@ fn snake_spawn(
 }
 
 fn snake_movement_input(
+    game_state: Res<GameState>,
+    clock: Res<Clock>,
     keyboard_input: Res<Input<KeyCode>>,
+    mut input_log: ResMut<InputLog>,
     mut snake_heads: Query<&mut SnakeHead>,
 ) {
+    if *game_state != GameState::Playing {
+        return;
+    }
     for mut snake_head in snake_heads.iter_mut() {
         let direction: Direction = if keyboard_input.pressed(KeyCode::Left) {
             Direction::Left
@@ -143,22 +294,203 @@ fn snake_movement_input(
         } else {
             snake_head.direction
         };
-        if direction != snake_head.direction.opposite() {
+        if direction != snake_head.direction.opposite() && direction != snake_head.next_direction {
+            input_log.0.push((clock.ticks, direction));
             snake_head.next_direction = direction;
         }
     }
 }
 
+fn snake_movement_replay(
+    game_state: Res<GameState>,
+    clock: Res<Clock>,
+    mut replay_log: ResMut<ReplayLog>,
+    mut snake_heads: Query<&mut SnakeHead>,
+) {
+    if *game_state != GameState::Playing {
+        return;
+    }
+    while let Some(&(tick, direction)) = replay_log.entries.get(replay_log.cursor) {
+        if tick > clock.ticks {
+            break;
+        }
+        for mut snake_head in snake_heads.iter_mut() {
+            if direction != snake_head.direction.opposite() {
+                snake_head.next_direction = direction;
+            }
+        }
+        replay_log.cursor += 1;
+    }
+}
+
 fn snake_movement(
+    game_state: Res<GameState>,
     mut snake_heads: Query<(&mut SnakeHead, &mut GridPosition)>,
     mut grid_positions: Query<&mut GridPosition, Without<SnakeHead>>,
+    mut last_tail_position: ResMut<LastTailPosition>,
 ) {
+    if *game_state != GameState::Playing {
+        return;
+    }
     for (mut snake_head, mut grid_position) in snake_heads.iter_mut() {
         snake_head.direction = snake_head.next_direction;
         let direction_vector = snake_head.direction.vec();
+        last_tail_position.0 = snake_head.tail_position(&grid_positions);
         snake_head.update_segment_positions(&grid_position, &mut grid_positions);
-        grid_position.x = (grid_position.x as f32 + direction_vector.x) as u32;
-        grid_position.y = (grid_position.y as f32 + direction_vector.y) as u32;
+        grid_position.x = (grid_position.x as f32 + direction_vector.x) as i32;
+        grid_position.y = (grid_position.y as f32 + direction_vector.y) as i32;
+    }
+}
+
+fn collision_detection(
+    game_state: Res<GameState>,
+    dimensions: Res<GridDimensions>,
+    mut game_over_events: EventWriter<GameOverEvent>,
+    snake_heads: Query<(&SnakeHead, &GridPosition)>,
+    walls: Query<&GridPosition, With<Wall>>,
+    segments: Query<&GridPosition, With<SnakeSegment>>,
+) {
+    if *game_state != GameState::Playing {
+        return;
+    }
+    for (snake_head, grid_position) in snake_heads.iter() {
+        let direction_vector = snake_head.next_direction.vec();
+        let next_position = GridPosition::new(
+            grid_position.x + direction_vector.x as i32,
+            grid_position.y + direction_vector.y as i32,
+        );
+        let hits_wall = walls.iter().any(|wall| wall.x == next_position.x && wall.y == next_position.y);
+        // The tail vacates its cell on the same tick the head would move into
+        // it, so following your own tail is legal and must not be flagged.
+        let tail = snake_head.segments.last().copied();
+        let hits_self = snake_head.segments.iter().any(|&segment_entity| {
+            Some(segment_entity) != tail
+                && segments.get(segment_entity).map_or(false, |segment_position| {
+                    segment_position.x == next_position.x && segment_position.y == next_position.y
+                })
+        });
+        if !next_position.in_bounds(&dimensions) || hits_wall || hits_self {
+            game_over_events.send(GameOverEvent);
+        }
+    }
+}
+
+fn handle_game_over(
+    mut game_over_events: EventReader<GameOverEvent>,
+    mut game_state: ResMut<GameState>,
+) {
+    if game_over_events.iter().next().is_some() {
+        *game_state = GameState::GameOver;
+    }
+}
+
+fn restart(
+    mut commands: Commands,
+    materials: Res<Materials>,
+    dimensions: Res<GridDimensions>,
+    spawn_point: Res<SpawnPoint>,
+    game_seed: Res<GameSeed>,
+    input_log: Option<ResMut<InputLog>>,
+    mut food_rng: ResMut<FoodRng>,
+    mut food_spawn_timer: ResMut<FoodSpawnTimer>,
+    mut game_state: ResMut<GameState>,
+    mut last_tail_position: ResMut<LastTailPosition>,
+    mut score: ResMut<Score>,
+    mut movement_interval: ResMut<MovementInterval>,
+    mut clock: ResMut<Clock>,
+    despawn: Query<Entity, Or<(With<SnakeHead>, With<SnakeSegment>, With<Food>)>>,
+) {
+    if *game_state != GameState::GameOver {
+        return;
+    }
+    if let Some(mut input_log) = input_log {
+        match json5::to_string(&input_log.0) {
+            Ok(replay) => println!(
+                "Run ended. Seed {} plus this input log reproduce it exactly:\n{}",
+                game_seed.0, replay,
+            ),
+            Err(error) => eprintln!("Failed to serialize input log: {}", error),
+        }
+        input_log.0.clear();
+    }
+    for entity in despawn.iter() {
+        commands.entity(entity).despawn();
+    }
+    spawn_snake(&mut commands, &materials, &dimensions, &spawn_point);
+    last_tail_position.0 = None;
+    *score = Score::default();
+    *movement_interval = MovementInterval::default();
+    *clock = Clock::default();
+    *food_rng = FoodRng(Pcg64::seed_from_u64(game_seed.0));
+    *food_spawn_timer = FoodSpawnTimer::default();
+    *game_state = GameState::Playing;
+}
+
+fn food_spawn(
+    game_state: Res<GameState>,
+    mut commands: Commands,
+    materials: Res<Materials>,
+    dimensions: Res<GridDimensions>,
+    mut timer: ResMut<FoodSpawnTimer>,
+    mut food_rng: ResMut<FoodRng>,
+    food: Query<&GridPosition, With<Food>>,
+    occupied: Query<&GridPosition, Or<(With<SnakeHead>, With<SnakeSegment>, With<Wall>)>>,
+) {
+    if *game_state != GameState::Playing {
+        return;
+    }
+    if timer.ticks_remaining > 0 {
+        timer.ticks_remaining -= 1;
+        return;
+    }
+    timer.ticks_remaining = FOOD_SPAWN_INTERVAL_TICKS;
+    if food.iter().next().is_some() {
+        return;
+    }
+    let taken: Vec<&GridPosition> = occupied.iter().collect();
+    let grid_position = loop {
+        let candidate = GridPosition::new(
+            food_rng.0.gen_range(0..dimensions.width as i32),
+            food_rng.0.gen_range(0..dimensions.height as i32),
+        );
+        if !taken.iter().any(|position| position.x == candidate.x && position.y == candidate.y) {
+            break candidate;
+        }
+    };
+    commands
+        .spawn_bundle(SpriteBundle {
+            material: materials.food.clone(),
+            sprite: Sprite::new(Vec2::new(GRID_SCALE as f32 * 0.5, GRID_SCALE as f32 * 0.5)),
+            transform: Transform::from_translation(grid_to_vector(&grid_position, &dimensions)),
+            ..Default::default()
+        })
+        .insert(Food)
+        .insert(grid_position);
+}
+
+fn food_eating(
+    game_state: Res<GameState>,
+    mut commands: Commands,
+    materials: Res<Materials>,
+    dimensions: Res<GridDimensions>,
+    mut snake_heads: Query<(&mut SnakeHead, &GridPosition)>,
+    food: Query<(Entity, &GridPosition), With<Food>>,
+    last_tail_position: Res<LastTailPosition>,
+    mut growth_events: EventWriter<GrowthEvent>,
+) {
+    if *game_state != GameState::Playing {
+        return;
+    }
+    for (mut snake_head, head_position) in snake_heads.iter_mut() {
+        for (food_entity, food_position) in food.iter() {
+            if head_position.x == food_position.x && head_position.y == food_position.y {
+                commands.entity(food_entity).despawn();
+                growth_events.send(GrowthEvent);
+                if let Some(tail_position) = last_tail_position.0.clone() {
+                    snake_head.spawn_segment(&mut commands, &materials, &dimensions, tail_position);
+                }
+            }
+        }
     }
 }
 
@@ -168,8 +500,44 @@ fn tick(
     clock.tick();
 }
 
-#[derive(PartialEq, Copy, Clone)]
-enum Direction {
+fn movement_timestep(
+    time: Res<Time>,
+    interval: Res<MovementInterval>,
+    mut elapsed: Local<f64>,
+) -> ShouldRun {
+    *elapsed += time.delta_seconds_f64();
+    if *elapsed >= interval.0 {
+        *elapsed -= interval.0;
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
+fn update_difficulty(
+    mut growth_events: EventReader<GrowthEvent>,
+    mut score: ResMut<Score>,
+    mut interval: ResMut<MovementInterval>,
+) {
+    for _ in growth_events.iter() {
+        score.eaten += 1;
+        interval.0 = BASE_MOVEMENT_INTERVAL / (1.0 + score.eaten as f64 * DIFFICULTY_FACTOR);
+    }
+}
+
+fn score_display(
+    clock: Res<Clock>,
+    snake_heads: Query<&SnakeHead>,
+    mut texts: Query<&mut Text, With<ScoreText>>,
+) {
+    let length = snake_heads.iter().next().map_or(0, |head| head.segments.len() + 1);
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = format!("Length: {}  Ticks: {}", length, clock.ticks);
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub(crate) enum Direction {
     Left,
     Right,
     Down,
@@ -215,14 +583,15 @@ impl SnakeHead {
     fn spawn_segment(
         &mut self,
         commands: &mut Commands,
-        materials: &Res<Materials>,
+        materials: &Materials,
+        dimensions: &GridDimensions,
         grid_position: GridPosition,
     ) {
         self.segments.push(commands
             .spawn_bundle(SpriteBundle {
                 material: materials.snake.clone(),
                 sprite: Sprite::new(Vec2::new(GRID_SCALE as f32 * 0.75, GRID_SCALE as f32 * 0.75)),
-                transform: Transform::from_translation(grid_to_vector(&grid_position)),
+                transform: Transform::from_translation(grid_to_vector(&grid_position, dimensions)),
                 ..Default::default()
             })
             .insert(SnakeSegment)
@@ -230,6 +599,14 @@ impl SnakeHead {
             .id()
         );
     }
+    fn tail_position(
+        &self,
+        grid_positions: &Query<&mut GridPosition, Without<SnakeHead>>,
+    ) -> Option<GridPosition> {
+        self.segments
+            .last()
+            .map(|tail| grid_positions.get(*tail).unwrap().clone())
+    }
     fn update_segment_positions(
         &mut self,
         head_position: &GridPosition,
@@ -253,25 +630,94 @@ impl SnakeHead {
 
 struct SnakeSegment;
 
+struct Wall;
+
+struct Food;
+
+struct GrowthEvent;
+
+struct GameOverEvent;
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum GameState {
+    Playing,
+    GameOver,
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::Playing
+    }
+}
+
+struct GameSeed(u64);
+
+struct FoodRng(Pcg64);
+
+// Every direction change the player made, keyed by the `Clock` tick it happened on.
+#[derive(Default, Serialize)]
+struct InputLog(Vec<(u32, Direction)>);
+
+// A previously recorded `InputLog`, played back instead of reading the keyboard.
+struct ReplayLog {
+    entries: Vec<(u32, Direction)>,
+    cursor: usize,
+}
+
+struct ScoreText;
+
+#[derive(Default)]
+struct Score {
+    eaten: u32,
+}
+
+struct MovementInterval(f64);
+
+impl Default for MovementInterval {
+    fn default() -> Self {
+        Self(BASE_MOVEMENT_INTERVAL)
+    }
+}
+
+#[derive(Default)]
+struct LastTailPosition(Option<GridPosition>);
+
+// Counts down movement ticks until the next food spawn attempt, so placement
+// stays reproducible from a seed rather than drifting with the frame rate.
+struct FoodSpawnTimer {
+    ticks_remaining: u32,
+}
+
+impl Default for FoodSpawnTimer {
+    fn default() -> Self {
+        Self { ticks_remaining: FOOD_SPAWN_INTERVAL_TICKS }
+    }
+}
+
+struct SpawnPoint {
+    position: GridPosition,
+    direction: Direction,
+}
+
+#[derive(Clone, Copy)]
+struct GridDimensions {
+    width: u32,
+    height: u32,
+}
+
 #[derive(Default, Clone)]
 struct GridPosition {
-    x: u32,
-    y: u32,
+    x: i32,
+    y: i32,
     t: Option<f32>,
 }
 
 impl GridPosition {
-    fn new(x: u32, y: u32) -> Self {
+    fn new(x: i32, y: i32) -> Self {
         Self { x, y, t: Some(0.375) }
     }
-    fn center() -> Self {
-        Self::new(
-            (GRID_WIDTH as f32 / 2.0) as u32,
-            (GRID_HEIGHT as f32 / 2.0) as u32,
-        )
-    }
-    fn in_bounds(&self) -> bool {
-        self.x < GRID_WIDTH && self.y < GRID_HEIGHT
+    fn in_bounds(&self, dimensions: &GridDimensions) -> bool {
+        self.x >= 0 && self.y >= 0 && self.x < dimensions.width as i32 && self.y < dimensions.height as i32
     }
 }
 
@@ -291,6 +737,7 @@ struct Materials {
     grid_background: Handle<ColorMaterial>,
     snake: Handle<ColorMaterial>,
     food: Handle<ColorMaterial>,
+    wall: Handle<ColorMaterial>,
 }
 
 impl Materials {
@@ -299,6 +746,7 @@ impl Materials {
             grid_background: materials.add(Color::hex(theme::GRID_BACKGROUND).unwrap().into()),
             snake: materials.add(Color::hex(theme::SNAKE).unwrap().into()),
             food: materials.add(Color::hex(theme::FOOD).unwrap().into()),
+            wall: materials.add(Color::hex(theme::WALL).unwrap().into()),
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,12 @@
+// Named palettes built on top of `colors`, selected by `main` for drawing
+// the window background, grid, snake, food and walls.
+
+pub mod dracula {
+    use crate::colors;
+
+    pub const BACKGROUND: &str = colors::BACKGROUND;
+    pub const GRID_BACKGROUND: &str = colors::COMMENT;
+    pub const SNAKE: &str = colors::GREEN;
+    pub const FOOD: &str = colors::RED;
+    pub const WALL: &str = colors::PURPLE;
+}